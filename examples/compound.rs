@@ -32,6 +32,7 @@ impl Asset for Person {
 // Bonus: how to implement `GgezAsset` if you need it
 
 /// The "raw" value, that doesn't require a context
+#[derive(Clone)]
 struct PersonRaw {
     name: String,
     avatar: <ggez::graphics::Image as GgezAsset>::Raw,
@@ -29,6 +29,16 @@ impl MainState {
 
 impl event::EventHandler for MainState {
     fn update(&mut self, _ctx: &mut ggez::Context) -> GameResult<()> {
+        for failure in self.cache.drain_load_failures() {
+            log::error!(
+                "Failed to load `{}` ({}) after {} attempt(s): {}",
+                failure.id,
+                failure.type_name,
+                failure.attempts,
+                failure.error
+            );
+        }
+
         Ok(())
     }
 
@@ -65,15 +75,11 @@ impl event::EventHandler for MainState {
         repeated: bool,
     ) -> GameResult<()> {
         if input.event.physical_key == ggez::input::keyboard::KeyCode::Space && !repeated {
-            match self
+            if let Ok(source) = self
                 .cache
                 .ggez_load_init::<audio::Source>(ctx, "audio.on_key")
             {
-                Ok(source) => source.play(),
-                Err(err) => {
-                    static LOGGED: std::sync::Once = std::sync::Once::new();
-                    LOGGED.call_once(|| log::error!("Failed to load sound: {}", err));
-                }
+                source.play();
             }
         }
 
@@ -1,5 +1,5 @@
-use assets_manager::{AssetCache, BoxedError, FileAsset, OnceInitCell, ReloadWatcher};
-use std::{borrow::Cow, io, sync::Mutex};
+use assets_manager::{Asset, AssetCache, BoxedError, FileAsset, OnceInitCell, ReloadWatcher};
+use std::{borrow::Cow, collections::HashMap, io, sync::Mutex};
 
 #[cold]
 fn convert_error(err: assets_manager::Error) -> ggez::GameError {
@@ -165,6 +165,7 @@ impl<T: NewWithGgezContext> GgezAsset for T {
     }
 }
 
+#[derive(Clone)]
 pub struct ImageAsset(Vec<u8>);
 
 impl FileAsset for ImageAsset {
@@ -183,6 +184,7 @@ impl NewWithGgezContext for ggez::graphics::Image {
     }
 }
 
+#[derive(Clone)]
 pub struct ShaderAsset(String);
 
 impl FileAsset for ShaderAsset {
@@ -297,3 +299,184 @@ impl GgezAsset for ggez::audio::SpatialSource {
         Self::from_data(context, sound.0)
     }
 }
+
+/// A pixel rectangle inside a [`SpriteSheet`], as found in its RON descriptor.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+struct FrameRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+/// The RON document listing the frames of a [`SpriteSheet`].
+///
+/// Points at the image it dices up through `image`, so the two files can
+/// live and reload independently.
+#[derive(serde::Deserialize, assets_manager::Asset)]
+#[asset_format = "ron"]
+struct SpriteSheetDescriptor {
+    image: assets_manager::SharedString,
+    frames: HashMap<String, FrameRect>,
+}
+
+/// The raw content of a [`SpriteSheet`]: the descriptor and the image bytes
+/// it refers to.
+#[derive(Clone)]
+pub struct SpriteSheetRaw {
+    image: ImageAsset,
+    frames: HashMap<String, FrameRect>,
+}
+
+impl Asset for SpriteSheetRaw {
+    fn load(cache: &AssetCache, id: &assets_manager::SharedString) -> Result<Self, BoxedError> {
+        // Go through `cache.load` rather than `Asset::load` directly, so the
+        // dependency on the descriptor and the image it points at is
+        // recorded: editing either one then reloads this atlas.
+        let (image_id, frames) = {
+            let descriptor = cache.load::<SpriteSheetDescriptor>(id)?.read();
+            (descriptor.image.clone(), descriptor.frames.clone())
+        };
+        let image = cache.load::<ImageAsset>(&image_id)?.read().clone();
+        Ok(SpriteSheetRaw { image, frames })
+    }
+}
+
+/// A texture atlas: a single [`ggez::graphics::Image`] paired with a table of
+/// named sub-rectangles, described by a sibling RON file (see
+/// [`SpriteSheetDescriptor`]).
+pub struct SpriteSheet {
+    image: ggez::graphics::Image,
+    width: u32,
+    height: u32,
+    frames: HashMap<String, FrameRect>,
+}
+
+impl crate::GgezAsset for SpriteSheet {
+    type Raw = SpriteSheetRaw;
+
+    fn from_raw(raw: &mut Self::Raw, ctx: &mut ggez::Context) -> ggez::GameResult<Self> {
+        let image = ggez::graphics::Image::from_bytes(ctx, &raw.image.0)?;
+        let (width, height) = (image.width(), image.height());
+
+        for (name, frame) in &raw.frames {
+            if frame.x.saturating_add(frame.w) > width || frame.y.saturating_add(frame.h) > height
+            {
+                return Err(ggez::GameError::ResourceLoadError(format!(
+                    "sprite sheet frame \"{name}\" is out of the image's bounds"
+                )));
+            }
+        }
+
+        Ok(SpriteSheet {
+            image,
+            width,
+            height,
+            frames: std::mem::take(&mut raw.frames),
+        })
+    }
+}
+
+impl SpriteSheet {
+    /// Returns the image containing every frame.
+    pub fn image(&self) -> &ggez::graphics::Image {
+        &self.image
+    }
+
+    /// Returns the rectangle of a named frame, normalized to `0.0..1.0`.
+    ///
+    /// This is the format expected by [`ggez::graphics::DrawParam::src`].
+    pub fn frame(&self, name: &str) -> Option<ggez::graphics::Rect> {
+        let f = self.frames.get(name)?;
+        Some(ggez::graphics::Rect::new(
+            f.x as f32 / self.width as f32,
+            f.y as f32 / self.height as f32,
+            f.w as f32 / self.width as f32,
+            f.h as f32 / self.height as f32,
+        ))
+    }
+
+    /// Returns the `[x, y, w, h]` rectangle of a named frame, in pixels.
+    pub fn frame_px(&self, name: &str) -> Option<[u32; 4]> {
+        let f = self.frames.get(name)?;
+        Some([f.x, f.y, f.w, f.h])
+    }
+}
+
+/// The RON document describing a [`Scene`]: named lists of assets, by id.
+#[derive(serde::Deserialize, assets_manager::Asset)]
+#[asset_format = "ron"]
+struct SceneDescriptor {
+    #[serde(default)]
+    textures: Vec<(String, assets_manager::SharedString)>,
+    #[serde(default)]
+    sounds: Vec<(String, assets_manager::SharedString)>,
+    #[serde(default)]
+    shaders: Vec<(String, assets_manager::SharedString)>,
+}
+
+/// A manifest that batch-loads a declared set of assets and exposes them by
+/// name, described by a RON document (see [`SceneDescriptor`]).
+///
+/// Loading a `Scene` doesn't need a [`ggez::Context`]: every asset is kept as
+/// a deferred [`ArcGgezHandle`](crate::ArcGgezHandle), the same way
+/// [`GgezStorage`](crate::GgezStorage) defers its own initialization, and is
+/// only materialized the first time it's read with a context.
+pub struct Scene {
+    textures: HashMap<String, crate::ArcGgezHandle<ggez::graphics::Image>>,
+    sounds: HashMap<String, crate::ArcGgezHandle<ggez::audio::Source>>,
+    shaders: HashMap<String, crate::ArcGgezHandle<ggez::graphics::Shader>>,
+}
+
+impl Asset for Scene {
+    fn load(cache: &AssetCache, id: &assets_manager::SharedString) -> Result<Self, BoxedError> {
+        let descriptor = SceneDescriptor::load(cache, id)?;
+
+        let mut textures = HashMap::with_capacity(descriptor.textures.len());
+        for (name, id) in descriptor.textures {
+            let handle = cache
+                .load::<crate::GgezStorage<ggez::graphics::Image>>(&id)?
+                .strong();
+            textures.insert(name, handle);
+        }
+
+        let mut sounds = HashMap::with_capacity(descriptor.sounds.len());
+        for (name, id) in descriptor.sounds {
+            let handle = cache
+                .load::<crate::GgezStorage<ggez::audio::Source>>(&id)?
+                .strong();
+            sounds.insert(name, handle);
+        }
+
+        let mut shaders = HashMap::with_capacity(descriptor.shaders.len());
+        for (name, id) in descriptor.shaders {
+            let handle = cache
+                .load::<crate::GgezStorage<ggez::graphics::Shader>>(&id)?
+                .strong();
+            shaders.insert(name, handle);
+        }
+
+        Ok(Scene {
+            textures,
+            sounds,
+            shaders,
+        })
+    }
+}
+
+impl Scene {
+    /// Returns the handle of a named texture, if the manifest declared one.
+    pub fn image(&self, name: &str) -> Option<&crate::ArcGgezHandle<ggez::graphics::Image>> {
+        self.textures.get(name)
+    }
+
+    /// Returns the handle of a named sound, if the manifest declared one.
+    pub fn sound(&self, name: &str) -> Option<&crate::ArcGgezHandle<ggez::audio::Source>> {
+        self.sounds.get(name)
+    }
+
+    /// Returns the handle of a named shader, if the manifest declared one.
+    pub fn shader(&self, name: &str) -> Option<&crate::ArcGgezHandle<ggez::graphics::Shader>> {
+        self.shaders.get(name)
+    }
+}
@@ -0,0 +1,129 @@
+//! A small mixer layered on top of the raw audio asset loading from
+//! [`crate::assets`].
+
+use std::{collections::HashMap, sync::Mutex};
+
+use ggez::{
+    audio::{SoundSource, Source, SpatialSource},
+    GameResult,
+};
+
+use crate::{assets::GgezAsset as _, AssetCache};
+
+/// Whether a sound should be played back as a plain [`Source`] or as a
+/// positional [`SpatialSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundInterpretation {
+    /// Played back as a non-positional [`Source`].
+    Generic,
+    /// Played back as a positional [`SpatialSource`].
+    Spatial,
+}
+
+enum Playing {
+    Generic(Source),
+    Spatial(SpatialSource),
+}
+
+impl Playing {
+    fn set_volume(&mut self, volume: f32) {
+        match self {
+            Playing::Generic(s) => s.set_volume(volume),
+            Playing::Spatial(s) => s.set_volume(volume),
+        }
+    }
+
+    fn stopped(&self) -> bool {
+        match self {
+            Playing::Generic(s) => s.stopped(),
+            Playing::Spatial(s) => s.stopped(),
+        }
+    }
+
+    fn play(&mut self) -> GameResult<()> {
+        match self {
+            Playing::Generic(s) => s.play(),
+            Playing::Spatial(s) => s.play(),
+        }
+    }
+}
+
+/// Tracks a master volume and a set of named category gains (e.g. `"sfx"`,
+/// `"music"`), and plays sounds at `master * category` volume.
+///
+/// Sounds are loaded through the [`GgezAsset`](crate::assets::GgezAsset)
+/// audio impls' fast path, so playing the same id many times doesn't
+/// re-decode it.
+pub struct VolumeHandler {
+    master_volume: f32,
+    categories: Mutex<HashMap<String, f32>>,
+    playing: Mutex<Vec<(String, Playing)>>,
+}
+
+impl VolumeHandler {
+    /// Creates a new handler with the given master volume. Categories
+    /// default to a gain of `1.0` until overridden with
+    /// [`set_category_volume`](Self::set_category_volume).
+    pub fn new(master_volume: f32) -> Self {
+        Self {
+            master_volume,
+            categories: Mutex::new(HashMap::new()),
+            playing: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn category_volume(&self, category: &str) -> f32 {
+        self.categories
+            .lock()
+            .unwrap()
+            .get(category)
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Sets the gain of a named category, rescaling the volume of every
+    /// sound of that category that is currently playing.
+    pub fn set_category_volume(&self, category: &str, volume: f32) {
+        self.categories
+            .lock()
+            .unwrap()
+            .insert(category.to_owned(), volume);
+
+        let mut playing = self.playing.lock().unwrap();
+        for (sound_category, sound) in playing.iter_mut() {
+            if sound_category == category {
+                sound.set_volume(self.master_volume * volume);
+            }
+        }
+    }
+
+    /// Loads a sound and plays it at `master * category` volume, choosing
+    /// between a [`Source`] and a [`SpatialSource`] based on
+    /// `interpretation`.
+    pub fn play(
+        &self,
+        cache: &AssetCache,
+        ctx: &mut ggez::Context,
+        id: &str,
+        category: &str,
+        interpretation: SoundInterpretation,
+    ) -> GameResult<()> {
+        let volume = self.master_volume * self.category_volume(category);
+
+        let mut sound = match interpretation {
+            SoundInterpretation::Generic => Playing::Generic(Source::load_fast(cache, ctx, id)?),
+            SoundInterpretation::Spatial => {
+                Playing::Spatial(SpatialSource::load_fast(cache, ctx, id)?)
+            }
+        };
+
+        sound.set_volume(volume);
+        sound.play()?;
+
+        let mut playing = self.playing.lock().unwrap();
+        playing.retain(|(_, s)| !s.stopped());
+        playing.push((category.to_owned(), sound));
+
+        Ok(())
+    }
+}
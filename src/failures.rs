@@ -0,0 +1,119 @@
+//! A per-[`AssetCache`] queue of failed [`GgezAsset`] loads, so games can
+//! build their own retry/backoff policy instead of silently swallowing load
+//! errors.
+
+use std::{
+    any::type_name,
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+use assets_manager::{AssetCache, BoxedError, SharedString};
+
+use crate::{AssetCacheExt, GgezAsset};
+
+/// A single failed attempt at loading a [`GgezAsset`], recorded by
+/// [`AssetCacheExt::ggez_load_init`] and [`AssetCacheExt::ggez_get_init`].
+///
+/// Drain these with [`AssetCacheExt::drain_load_failures`], or let
+/// [`AssetCacheExt::retry_failed`] attempt the failed ids again.
+pub struct LoadFailure {
+    /// The id that failed to load.
+    pub id: SharedString,
+    /// The name of the [`GgezAsset`] type that was being loaded.
+    pub type_name: &'static str,
+    /// The error returned by the last failed attempt.
+    pub error: BoxedError,
+    /// How many times this id has failed while its failure was pending in the
+    /// queue. A successful load does not reset it; draining the failure with
+    /// [`AssetCacheExt::drain_load_failures`] does, so the next failure counts
+    /// from 1 again.
+    pub attempts: u32,
+    retry: Box<dyn FnMut(&AssetCache, &mut ggez::Context) -> ggez::GameResult<()> + Send>,
+}
+
+impl std::fmt::Debug for LoadFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadFailure")
+            .field("id", &self.id)
+            .field("type_name", &self.type_name)
+            .field("error", &self.error)
+            .field("attempts", &self.attempts)
+            .finish_non_exhaustive()
+    }
+}
+
+type Key = (SharedString, &'static str);
+type Queue = Mutex<HashMap<Key, LoadFailure>>;
+
+/// Associates a failure queue to each [`AssetCache`], keyed by its address.
+///
+/// Games only ever create one long-lived `AssetCache`, so leaking the small,
+/// bounded number of queues this creates over a process' lifetime is fine.
+fn queue_for(cache: &AssetCache) -> &'static Queue {
+    static QUEUES: OnceLock<Mutex<HashMap<usize, &'static Queue>>> = OnceLock::new();
+
+    let queues = QUEUES.get_or_init(Default::default);
+    let key = std::ptr::from_ref(cache) as usize;
+
+    *queues
+        .lock()
+        .unwrap()
+        .entry(key)
+        .or_insert_with(|| Box::leak(Box::new(Mutex::new(HashMap::new()))))
+}
+
+/// Records a failed load, bumping `attempts` if this id/type already had a
+/// pending failure.
+pub(crate) fn record<T: GgezAsset>(cache: &AssetCache, id: &str, error: &ggez::GameError) {
+    let mut queue = queue_for(cache).lock().unwrap();
+    let key = (SharedString::from(id), type_name::<T>());
+    let attempts = queue.get(&key).map_or(0, |failure| failure.attempts) + 1;
+    let id_for_retry = key.0.clone();
+
+    queue.insert(
+        key.clone(),
+        LoadFailure {
+            id: key.0,
+            type_name: key.1,
+            error: error.to_string().into(),
+            attempts,
+            retry: Box::new(move |cache, ctx| {
+                cache
+                    .ggez_load_init::<T>(ctx, &id_for_retry)
+                    .map(drop)
+            }),
+        },
+    );
+}
+
+pub(crate) fn drain(cache: &AssetCache) -> Vec<LoadFailure> {
+    queue_for(cache)
+        .lock()
+        .unwrap()
+        .drain()
+        .map(|(_, failure)| failure)
+        .collect()
+}
+
+pub(crate) fn retry_failed(
+    cache: &AssetCache,
+    ctx: &mut ggez::Context,
+    should_retry: &mut dyn FnMut(&LoadFailure) -> bool,
+) {
+    for mut failure in drain(cache) {
+        if should_retry(&failure) {
+            if let Err(error) = (failure.retry)(cache, ctx) {
+                failure.attempts += 1;
+                failure.error = error.to_string().into();
+            } else {
+                continue;
+            }
+        }
+
+        queue_for(cache)
+            .lock()
+            .unwrap()
+            .insert((failure.id.clone(), failure.type_name), failure);
+    }
+}
@@ -4,14 +4,30 @@
 #![warn(missing_docs)]
 
 mod assets;
+mod audio;
+mod failures;
+#[cfg(feature = "gltf")]
+mod mesh;
+mod prefetch;
+mod preload;
 mod source;
+#[cfg(feature = "svg")]
+mod svg;
 
-pub use assets::convert_error;
+pub use assets::{convert_error, Scene, SpriteSheet};
 pub use assets_manager::{self, AssetCache};
-pub use source::GgezFileSystem;
-
-use assets_manager::{ArcHandle, Asset, AssetReadGuard, Handle, OnceInitCell};
+pub use audio::{SoundInterpretation, VolumeHandler};
+pub use failures::LoadFailure;
+#[cfg(feature = "gltf")]
+pub use mesh::{GltfMesh, GltfRaw};
+pub use preload::{GgezPreloader, Progress};
+pub use source::{GgezFileSystem, OverlaySource, PackedSource};
+#[cfg(feature = "svg")]
+pub use svg::{SvgImage, SvgRaw};
+
+use assets_manager::{ArcHandle, Asset, Handle, ReloadWatcher};
 use ggez::GameResult;
+use std::sync::{Arc, Mutex};
 
 /// Assets that require a [`ggez::Context`] to be loaded.
 ///
@@ -19,29 +35,56 @@ use ggez::GameResult;
 /// part that requires  the `ggez::Context`.
 pub trait GgezAsset: Sized + Send + Sync + 'static {
     /// The raw value, that doesn't require a `ggez::Context`.
-    type Raw: Asset;
+    ///
+    /// `Clone` is required so [`GgezStorage`] can take a snapshot of the raw
+    /// value to rebuild `Self` from when it is hot-reloaded, without holding
+    /// a lock on it for the whole duration of [`GgezAsset::from_raw`].
+    type Raw: Asset + Clone;
 
     /// Converts the raw value to the the actual asset.
     fn from_raw(raw: &mut Self::Raw, ctx: &mut ggez::Context) -> GameResult<Self>;
 }
 
 /// Stores types that implement [`GgezAsset`].
-pub struct GgezStorage<T: GgezAsset>(OnceInitCell<T::Raw, T>);
+///
+/// The raw value is kept as a live [`ArcHandle`], so it always reflects the
+/// latest hot-reload. The context-dependent value built from it is cached,
+/// and [`get_or_init`](Self::get_or_init) rebuilds it with [`GgezAsset::from_raw`]
+/// whenever the raw value has changed since the last build.
+pub struct GgezStorage<T: GgezAsset> {
+    raw: ArcHandle<T::Raw>,
+    built: arc_swap::ArcSwapOption<T>,
+    watcher: Mutex<ReloadWatcher<'static>>,
+}
 
 impl<T: GgezAsset> GgezStorage<T> {
-    /// Creates a new uninitialized storage.
-    pub const fn new(raw: T::Raw) -> Self {
-        Self(OnceInitCell::new(raw))
+    fn new(raw: ArcHandle<T::Raw>) -> Self {
+        let watcher = raw.reload_watcher();
+        Self {
+            raw,
+            built: arc_swap::ArcSwapOption::empty(),
+            watcher: Mutex::new(watcher),
+        }
     }
 
-    /// Gets the value if it was initialized.
-    pub fn get(&self) -> Option<&T> {
-        self.0.get()
+    /// Gets the value if it was initialized, without checking whether the
+    /// raw asset has been hot-reloaded since.
+    pub fn get(&self) -> Option<Arc<T>> {
+        self.built.load_full()
     }
 
-    /// Get the value, initializing it if needed.
-    pub fn get_or_init(&self, ctx: &mut ggez::Context) -> GameResult<&T> {
-        self.0.get_or_try_init(|raw| T::from_raw(raw, ctx))
+    /// Gets the value, (re)initializing it with `ctx` if it is missing or
+    /// the raw asset has been hot-reloaded since the last build.
+    pub fn get_or_init(&self, ctx: &mut ggez::Context) -> GameResult<Arc<T>> {
+        let mut watcher = self.watcher.lock().unwrap();
+
+        if self.built.load().is_none() || watcher.reloaded() {
+            let mut raw = self.raw.read().clone();
+            let value = T::from_raw(&mut raw, ctx)?;
+            self.built.store(Some(Arc::new(value)));
+        }
+
+        Ok(self.built.load_full().unwrap())
     }
 }
 
@@ -50,7 +93,8 @@ impl<T: GgezAsset> Asset for GgezStorage<T> {
         cache: &AssetCache,
         id: &assets_manager::SharedString,
     ) -> Result<Self, assets_manager::BoxedError> {
-        Asset::load(cache, id).map(Self)
+        let raw = cache.load::<T::Raw>(id)?.strong();
+        Ok(Self::new(raw))
     }
 }
 
@@ -90,38 +134,43 @@ pub trait HandleExt: seal::Sealed {
     /// The actual asset type.
     type Target;
 
-    /// Locks the asset for reading and gets it if it was initialized.
-    fn read_get(&self) -> Option<AssetReadGuard<'_, Self::Target>>;
+    /// Gets the asset if it was initialized, without checking whether the
+    /// raw asset has been hot-reloaded since.
+    fn read_get(&self) -> Option<Arc<Self::Target>>;
 
-    /// Locks the asset for reading, initializing it if needed.
-    fn read_init(&self, ctx: &mut ggez::Context) -> GameResult<AssetReadGuard<'_, Self::Target>>;
+    /// Gets the asset, (re)initializing it with `ctx` if it is missing or
+    /// has been hot-reloaded since the last build.
+    fn read_init(&self, ctx: &mut ggez::Context) -> GameResult<Arc<Self::Target>>;
 
     /// Get a clone of the asset, initializing it if needed.
     fn get_cloned(&self, ctx: &mut ggez::Context) -> GameResult<Self::Target>
     where
         Self::Target: Clone,
     {
-        self.read_init(ctx).map(|g| g.clone())
+        self.read_init(ctx).map(|a| Self::Target::clone(&a))
+    }
+
+    /// Rebuilds the asset now if the raw data has been hot-reloaded since it
+    /// was last built, without requiring the caller to use the returned
+    /// value.
+    ///
+    /// This only rebuilds on the thread that owns `ctx`; readers that never
+    /// call this (or `read_init`) keep seeing the last successfully built
+    /// value.
+    fn reload_if_stale(&self, ctx: &mut ggez::Context) -> GameResult<()> {
+        self.read_init(ctx).map(|_| ())
     }
 }
 
 impl<T: GgezAsset> HandleExt for Handle<GgezStorage<T>> {
     type Target = T;
 
-    fn read_get(&self) -> Option<AssetReadGuard<'_, T>> {
-        AssetReadGuard::try_map(self.read(), |x| x.get()).ok()
+    fn read_get(&self) -> Option<Arc<T>> {
+        self.read().get()
     }
 
-    fn read_init(&self, ctx: &mut ggez::Context) -> GameResult<AssetReadGuard<'_, T>> {
-        let mut err = None;
-        AssetReadGuard::try_map(self.read(), |x| match x.get_or_init(ctx) {
-            Ok(x) => Some(x),
-            Err(e) => {
-                err = Some(e);
-                None
-            }
-        })
-        .map_err(|_| err.unwrap())
+    fn read_init(&self, ctx: &mut ggez::Context) -> GameResult<Arc<T>> {
+        self.read().get_or_init(ctx)
     }
 }
 
@@ -145,17 +194,12 @@ pub trait AssetCacheExt: seal::Sealed {
 
     /// Loads a `ggez` asset and initialize it.
     ///
-    /// The initialization is only done once per asset.
-    fn ggez_load_init<T>(
-        &self,
-        ctx: &mut ggez::Context,
-        id: &str,
-    ) -> GameResult<AssetReadGuard<'_, T>>
+    /// The initialization is only done once per asset, and is redone if the
+    /// raw asset is hot-reloaded. On failure, the error is also recorded; see
+    /// [`drain_load_failures`](Self::drain_load_failures).
+    fn ggez_load_init<T>(&self, ctx: &mut ggez::Context, id: &str) -> GameResult<Arc<T>>
     where
-        T: GgezAsset,
-    {
-        self.ggez_load(id)?.read_init(ctx)
-    }
+        T: GgezAsset;
 
     /// Loads a `ggez` asset, initialize and clone it.
     ///
@@ -164,7 +208,7 @@ pub trait AssetCacheExt: seal::Sealed {
     where
         T: GgezAsset + Clone,
     {
-        self.ggez_load(id)?.get_cloned(ctx)
+        self.ggez_load_init::<T>(ctx, id).map(|a| T::clone(&a))
     }
 
     /// Gets a `ggez` asset from the cache.
@@ -174,20 +218,12 @@ pub trait AssetCacheExt: seal::Sealed {
 
     /// Gets a `ggez` asset from the cache and initialize it.
     ///
-    /// The initialization is only done once per asset.
-    fn ggez_get_init<T>(
-        &self,
-        ctx: &mut ggez::Context,
-        id: &str,
-    ) -> GameResult<AssetReadGuard<'_, T>>
+    /// The initialization is only done once per asset, and is redone if the
+    /// raw asset is hot-reloaded. On failure, the error is also recorded; see
+    /// [`drain_load_failures`](Self::drain_load_failures).
+    fn ggez_get_init<T>(&self, ctx: &mut ggez::Context, id: &str) -> GameResult<Arc<T>>
     where
-        T: GgezAsset,
-    {
-        let not_found =
-            || ggez::GameError::ResourceLoadError("resource not found in cache".to_owned());
-
-        self.ggez_get(id).ok_or_else(not_found)?.read_init(ctx)
-    }
+        T: GgezAsset;
 
     /// Gets a `ggez` asset from the cache, initialize and clone it.
     ///
@@ -196,7 +232,7 @@ pub trait AssetCacheExt: seal::Sealed {
     where
         T: GgezAsset + Clone,
     {
-        self.ggez_get_init::<T>(ctx, id).map(|x| x.clone())
+        self.ggez_get_init::<T>(ctx, id).map(|x| T::clone(&x))
     }
 
     /// Returns `true` if an asset is present in the cache.
@@ -206,6 +242,48 @@ pub trait AssetCacheExt: seal::Sealed {
 
     /// Add a font to `ggez` with the given name, loaded from the given id.
     fn set_font(&self, context: &mut ggez::Context, name: &str, id: &str) -> GameResult<()>;
+
+    /// Drains the queue of failures accumulated by [`ggez_load_init`] and
+    /// [`ggez_get_init`], so they can be inspected, logged or retried.
+    ///
+    /// [`ggez_load_init`]: Self::ggez_load_init
+    /// [`ggez_get_init`]: Self::ggez_get_init
+    fn drain_load_failures(&self) -> Vec<LoadFailure>;
+
+    /// Retries every pending load failure that `should_retry` accepts,
+    /// re-queuing it (with a bumped `attempts`) if the retry fails again.
+    ///
+    /// This lets a game implement its own backoff policy on top of
+    /// [`LoadFailure::attempts`] instead of retrying blindly every frame.
+    fn retry_failed(
+        &self,
+        ctx: &mut ggez::Context,
+        should_retry: &mut dyn FnMut(&LoadFailure) -> bool,
+    );
+
+    /// Creates a [`GgezPreloader`] to batch-load a heterogeneous set of
+    /// assets ahead of time, e.g. behind a loading screen.
+    fn ggez_preloader(&self) -> GgezPreloader<'_>;
+
+    /// Starts decoding `T::Raw` for `id` on a background thread, so a later
+    /// call to [`ggez_load_init`](Self::ggez_load_init) only pays for the
+    /// cheap, context-bound [`GgezAsset::from_raw`] step on the main thread.
+    ///
+    /// Does nothing if a prefetch for this id and type is already in flight
+    /// or done. Requires `&'static self`, since the background thread must
+    /// outlive this call without resorting to unsafe lifetime tricks; this
+    /// is satisfied by an `AssetCache` stored for the life of the program,
+    /// e.g. behind a `OnceLock` or leaked once at startup.
+    fn ggez_prefetch<T>(&'static self, id: &str)
+    where
+        T: GgezAsset,
+        T::Raw: Send + Sync;
+
+    /// Returns `true` once a [`ggez_prefetch`](Self::ggez_prefetch) started
+    /// for `id` has finished decoding `T::Raw`.
+    fn poll_ready<T>(&self, id: &str) -> bool
+    where
+        T: GgezAsset;
 }
 
 impl AssetCacheExt for AssetCache {
@@ -223,6 +301,15 @@ impl AssetCacheExt for AssetCache {
             .map_err(crate::assets::convert_error)
     }
 
+    fn ggez_load_init<T>(&self, ctx: &mut ggez::Context, id: &str) -> GameResult<Arc<T>>
+    where
+        T: GgezAsset,
+    {
+        self.ggez_load(id)
+            .and_then(|handle| handle.read_init(ctx))
+            .inspect_err(|error| failures::record::<T>(self, id, error))
+    }
+
     #[inline]
     fn ggez_get<T>(&self, id: &str) -> Option<&Handle<GgezStorage<T>>>
     where
@@ -231,6 +318,19 @@ impl AssetCacheExt for AssetCache {
         self.get(id)
     }
 
+    fn ggez_get_init<T>(&self, ctx: &mut ggez::Context, id: &str) -> GameResult<Arc<T>>
+    where
+        T: GgezAsset,
+    {
+        let not_found =
+            || ggez::GameError::ResourceLoadError("resource not found in cache".to_owned());
+
+        self.ggez_get(id)
+            .ok_or_else(not_found)
+            .and_then(|handle| handle.read_init(ctx))
+            .inspect_err(|error| failures::record::<T>(self, id, error))
+    }
+
     #[inline]
     fn ggez_contains<T>(&self, id: &str) -> bool
     where
@@ -242,4 +342,40 @@ impl AssetCacheExt for AssetCache {
     fn set_font(&self, ctx: &mut ggez::Context, name: &str, id: &str) -> GameResult<()> {
         assets::set_font(self, ctx, name, id)
     }
+
+    #[inline]
+    fn drain_load_failures(&self) -> Vec<LoadFailure> {
+        failures::drain(self)
+    }
+
+    #[inline]
+    fn retry_failed(
+        &self,
+        ctx: &mut ggez::Context,
+        should_retry: &mut dyn FnMut(&LoadFailure) -> bool,
+    ) {
+        failures::retry_failed(self, ctx, should_retry)
+    }
+
+    #[inline]
+    fn ggez_preloader(&self) -> GgezPreloader<'_> {
+        GgezPreloader::new(self)
+    }
+
+    #[inline]
+    fn ggez_prefetch<T>(&'static self, id: &str)
+    where
+        T: GgezAsset,
+        T::Raw: Send + Sync,
+    {
+        prefetch::prefetch::<T>(self, id)
+    }
+
+    #[inline]
+    fn poll_ready<T>(&self, id: &str) -> bool
+    where
+        T: GgezAsset,
+    {
+        prefetch::poll_ready::<T>(self, id)
+    }
 }
@@ -0,0 +1,185 @@
+//! glTF mesh loading as a `GgezAsset`, gated behind the `gltf` feature.
+//!
+//! `ggez::graphics::Mesh` is a 2D mesh format, so only `POSITION` (projected
+//! onto the XY plane) and `TEXCOORD_0` are read; `NORMAL` data, if present,
+//! is not used. Buffers are read from a `.glb` binary chunk or from external
+//! files; embedded (`data:`) buffer URIs are rejected with an error.
+
+use std::borrow::Cow;
+
+use assets_manager::{asset::FileAsset, Asset, AssetCache, BoxedError, SharedString};
+
+/// The raw `.gltf`/`.glb` document bytes.
+struct GltfSource(Vec<u8>);
+
+impl FileAsset for GltfSource {
+    const EXTENSIONS: &'static [&'static str] = &["gltf", "glb"];
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Result<Self, BoxedError> {
+        Ok(GltfSource(bytes.into_owned()))
+    }
+}
+
+/// An external `.bin` buffer referenced by a glTF document's `uri`.
+struct GltfBuffer(Vec<u8>);
+
+impl FileAsset for GltfBuffer {
+    const EXTENSION: &'static str = "bin";
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Result<Self, BoxedError> {
+        Ok(GltfBuffer(bytes.into_owned()))
+    }
+}
+
+/// Resolves a glTF buffer's relative `uri` against `id`'s directory.
+fn sibling_id(id: &str, relative_path: &str) -> SharedString {
+    let parent = id.rsplit_once('.').map_or("", |(parent, _)| parent);
+    let path = std::path::Path::new(relative_path);
+
+    let mut out = parent.to_owned();
+    let mut push = |part: &str| {
+        if !out.is_empty() {
+            out.push('.');
+        }
+        out.push_str(part);
+    };
+
+    if let Some(dir) = path.parent() {
+        for comp in dir.components() {
+            if let std::path::Component::Normal(part) = comp {
+                if let Some(part) = part.to_str() {
+                    push(part);
+                }
+            }
+        }
+    }
+    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+        push(stem);
+    }
+
+    SharedString::from(out)
+}
+
+/// One mesh primitive's vertex/index data, pulled out of the glTF document
+/// ahead of time so building the `ggez` mesh doesn't need a `Context`.
+#[derive(Clone)]
+struct Primitive {
+    vertices: Vec<ggez::graphics::Vertex>,
+    indices: Vec<u32>,
+}
+
+fn read_primitive(
+    primitive: &gltf::Primitive<'_>,
+    buffers: &[Vec<u8>],
+) -> Result<Primitive, BoxedError> {
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(Vec::as_slice));
+
+    let positions: Vec<_> = reader
+        .read_positions()
+        .ok_or("glTF primitive is missing POSITION")?
+        .collect();
+
+    let uvs: Vec<_> = match reader.read_tex_coords(0) {
+        Some(uvs) => uvs.into_f32().collect(),
+        None => vec![[0.0, 0.0]; positions.len()],
+    };
+
+    let vertices = positions
+        .iter()
+        .zip(&uvs)
+        .map(|(position, uv)| ggez::graphics::Vertex {
+            position: [position[0], position[1]],
+            uv: *uv,
+            color: [1.0, 1.0, 1.0, 1.0],
+        })
+        .collect();
+
+    let indices = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect(),
+        None => (0..positions.len() as u32).collect(),
+    };
+
+    Ok(Primitive { vertices, indices })
+}
+
+/// The raw content of a [`GltfMesh`]: every primitive's vertex/index data,
+/// already resolved against external `.bin` buffers.
+#[derive(Clone)]
+pub struct GltfRaw {
+    primitives: Vec<Primitive>,
+}
+
+impl Asset for GltfRaw {
+    fn load(cache: &AssetCache, id: &SharedString) -> Result<Self, BoxedError> {
+        // Go through `cache.load` rather than `Asset::load` directly, so the
+        // dependency on the document and its external buffers is recorded:
+        // editing any of them reloads this mesh.
+        let bytes = cache.load::<GltfSource>(id)?.read().0.clone();
+        let gltf::Gltf {
+            document,
+            mut blob,
+        } = gltf::Gltf::from_slice(&bytes)?;
+
+        let buffers = document
+            .buffers()
+            .map(|buffer| -> Result<Vec<u8>, BoxedError> {
+                match buffer.source() {
+                    gltf::buffer::Source::Bin => {
+                        blob.take().ok_or_else(|| "missing glb BIN chunk".into())
+                    }
+                    gltf::buffer::Source::Uri(uri) if uri.starts_with("data:") => Err(
+                        "embedded (data:) glTF buffers are not supported; use an external .bin buffer"
+                            .into(),
+                    ),
+                    gltf::buffer::Source::Uri(uri) => {
+                        let buffer_id = sibling_id(id, uri);
+                        let data = cache.load::<GltfBuffer>(&buffer_id)?.read().0.clone();
+                        Ok(data)
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, BoxedError>>()?;
+
+        let primitives = document
+            .meshes()
+            .flat_map(|mesh| mesh.primitives())
+            .map(|primitive| read_primitive(&primitive, &buffers))
+            .collect::<Result<Vec<_>, BoxedError>>()?;
+
+        Ok(GltfRaw { primitives })
+    }
+}
+
+/// A glTF document's meshes, each primitive uploaded as its own
+/// [`ggez::graphics::Mesh`].
+pub struct GltfMesh(Vec<ggez::graphics::Mesh>);
+
+impl GltfMesh {
+    /// The mesh for each primitive found in the document, in document order.
+    #[must_use]
+    pub fn primitives(&self) -> &[ggez::graphics::Mesh] {
+        &self.0
+    }
+}
+
+impl crate::GgezAsset for GltfMesh {
+    type Raw = GltfRaw;
+
+    fn from_raw(raw: &mut Self::Raw, ctx: &mut ggez::Context) -> ggez::GameResult<Self> {
+        let meshes = raw
+            .primitives
+            .iter()
+            .map(|primitive| {
+                ggez::graphics::Mesh::from_data(
+                    ctx,
+                    ggez::graphics::MeshData {
+                        vertices: &primitive.vertices,
+                        indices: &primitive.indices,
+                    },
+                )
+            })
+            .collect();
+
+        Ok(GltfMesh(meshes))
+    }
+}
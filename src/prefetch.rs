@@ -0,0 +1,111 @@
+//! Background decoding of a [`GgezAsset::Raw`](crate::GgezAsset::Raw) value,
+//! so that [`crate::AssetCacheExt::ggez_load_init`]'s context-bound
+//! `from_raw` step only has to pay for cheap GPU/audio upload work on the
+//! main thread.
+
+use std::{
+    any::type_name,
+    collections::HashMap,
+    sync::{mpsc, Mutex, OnceLock},
+    thread,
+};
+
+use assets_manager::{AssetCache, SharedString};
+
+use crate::GgezAsset;
+
+type Key = (SharedString, &'static str);
+
+/// How many raw assets can be decoded at once across every prefetch.
+const POOL_SIZE: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The shared pool that actually runs prefetch jobs, so queuing a whole
+/// batch of ids doesn't spawn one OS thread per id.
+fn pool() -> &'static mpsc::Sender<Job> {
+    static POOL: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+
+    POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver: &'static Mutex<mpsc::Receiver<Job>> =
+            Box::leak(Box::new(Mutex::new(receiver)));
+
+        for _ in 0..POOL_SIZE {
+            thread::spawn(move || loop {
+                // Bind the result before matching on it, so the lock on the
+                // shared receiver is released before `job()` runs; otherwise
+                // the other workers would block on it for the whole job.
+                let job = receiver.lock().unwrap().recv();
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => break,
+                }
+            });
+        }
+
+        sender
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Pending,
+    Ready,
+    Failed,
+}
+
+/// Associates an in-flight/ready map to each [`AssetCache`], keyed by its
+/// address, the same way [`crate::failures`] tracks its own per-cache queue.
+fn state_map(cache: &AssetCache) -> &'static Mutex<HashMap<Key, State>> {
+    static MAPS: OnceLock<Mutex<HashMap<usize, &'static Mutex<HashMap<Key, State>>>>> =
+        OnceLock::new();
+
+    let maps = MAPS.get_or_init(Default::default);
+    let cache_key = std::ptr::from_ref(cache) as usize;
+
+    *maps
+        .lock()
+        .unwrap()
+        .entry(cache_key)
+        .or_insert_with(|| Box::leak(Box::new(Mutex::new(HashMap::new()))))
+}
+
+/// Queues a job on the background pool that decodes `T::Raw` for `id` and
+/// leaves it in `cache`, unless a prefetch for the same id/type is already
+/// in flight or done.
+///
+/// `cache` must be `'static` because the pool's worker threads have to
+/// outlive this call; this is a plain safe-Rust consequence of not holding
+/// unsafe lifetime-extended references across the thread boundary.
+pub(crate) fn prefetch<T>(cache: &'static AssetCache, id: &str)
+where
+    T: GgezAsset,
+    T::Raw: Send + Sync,
+{
+    let key: Key = (SharedString::from(id), type_name::<T>());
+
+    {
+        let mut state = state_map(cache).lock().unwrap();
+        if state.contains_key(&key) {
+            return;
+        }
+        state.insert(key.clone(), State::Pending);
+    }
+
+    let _ = pool().send(Box::new(move || {
+        let state = match cache.load::<T::Raw>(&key.0) {
+            Ok(_) => State::Ready,
+            Err(_) => State::Failed,
+        };
+        state_map(cache).lock().unwrap().insert(key, state);
+    }));
+}
+
+/// Returns `true` once a prefetch started for `id` has finished decoding
+/// `T::Raw` successfully. Returns `false` if it is still in flight, was
+/// never started, or failed.
+pub(crate) fn poll_ready<T: GgezAsset>(cache: &AssetCache, id: &str) -> bool {
+    let key: Key = (SharedString::from(id), type_name::<T>());
+    state_map(cache).lock().unwrap().get(&key) == Some(&State::Ready)
+}
@@ -0,0 +1,112 @@
+//! Batch-preloading of `ggez` assets with per-frame progress, so a loading
+//! screen can show a bar and gate the transition out of a `Startup` state.
+
+use assets_manager::AssetCache;
+
+use crate::{AssetCacheExt, GgezAsset};
+
+/// How many assets [`GgezPreloader::advance`] initializes per call, bounding
+/// the per-frame cost of a big batch so the event loop never stalls.
+const BATCH_SIZE: usize = 4;
+
+/// A snapshot of a [`GgezPreloader`]'s progress, suitable for driving a
+/// loading-screen bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// How many of the queued assets successfully loaded.
+    pub loaded: u32,
+    /// How many of the queued assets failed to load.
+    pub failed: u32,
+    /// How many assets were queued in total.
+    pub total: u32,
+}
+
+enum Slot {
+    Pending(Box<dyn FnMut(&AssetCache, &mut ggez::Context) -> bool>),
+    Done,
+}
+
+/// Queues a heterogeneous set of ids to be loaded and context-initialized,
+/// pumping a bounded number of them per [`advance`](Self::advance) call so a
+/// loading screen can stay responsive while it waits.
+pub struct GgezPreloader<'a> {
+    cache: &'a AssetCache,
+    slots: Vec<Slot>,
+    loaded: u32,
+    failed: u32,
+}
+
+impl<'a> GgezPreloader<'a> {
+    pub(crate) fn new(cache: &'a AssetCache) -> Self {
+        Self {
+            cache,
+            slots: Vec::new(),
+            loaded: 0,
+            failed: 0,
+        }
+    }
+
+    /// Queues a [`GgezAsset`] to be loaded and initialized.
+    pub fn add<T: GgezAsset>(&mut self, id: impl Into<String>) -> &mut Self {
+        let id = id.into();
+        self.slots.push(Slot::Pending(Box::new(move |cache, ctx| {
+            cache.ggez_load_init::<T>(ctx, &id).is_ok()
+        })));
+        self
+    }
+
+    /// Queues a font to be loaded and registered under `name`.
+    pub fn add_font(&mut self, name: impl Into<String>, id: impl Into<String>) -> &mut Self {
+        let name = name.into();
+        let id = id.into();
+        self.slots.push(Slot::Pending(Box::new(move |cache, ctx| {
+            cache.set_font(ctx, &name, &id).is_ok()
+        })));
+        self
+    }
+
+    /// The number of queued assets that are neither loaded nor failed yet.
+    pub fn remaining(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| matches!(slot, Slot::Pending(_)))
+            .count()
+    }
+
+    /// The current progress of this preloader.
+    pub fn progress(&self) -> Progress {
+        Progress {
+            loaded: self.loaded,
+            failed: self.failed,
+            total: self.slots.len() as u32,
+        }
+    }
+
+    /// Initializes up to a bounded number of still-pending assets.
+    ///
+    /// Returns `true` once every queued asset is either loaded or has
+    /// definitively failed, meaning the caller can stop calling `advance`.
+    pub fn advance(&mut self, ctx: &mut ggez::Context) -> bool {
+        let mut budget = BATCH_SIZE;
+
+        for slot in &mut self.slots {
+            if budget == 0 {
+                break;
+            }
+
+            let Slot::Pending(attempt) = slot else {
+                continue;
+            };
+
+            if attempt(self.cache, ctx) {
+                self.loaded += 1;
+            } else {
+                self.failed += 1;
+            }
+            *slot = Slot::Done;
+            budget -= 1;
+        }
+
+        self.remaining() == 0
+    }
+}
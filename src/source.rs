@@ -1,4 +1,8 @@
-use std::io;
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+};
 
 use assets_manager::{
     hot_reloading::{EventSender, FsWatcherBuilder},
@@ -9,7 +13,8 @@ use assets_manager::{
 ///
 /// See [`ggez::filesystem`] for more details.
 ///
-/// When hot-reloading is activated, changes to `"resources.zip"` are ignored.
+/// When hot-reloading is activated, changes inside `"resources.zip"` are
+/// detected and trigger a reload of the ids they affect.
 #[derive(Debug)]
 pub struct GgezFileSystem {
     fs: ggez::filesystem::Filesystem,
@@ -25,6 +30,13 @@ impl GgezFileSystem {
             fs: fs.retrieve().clone(),
         }
     }
+
+    /// The path to `resources.zip`, if `ggez` mounted one alongside the
+    /// loose `resources` directory.
+    fn resources_zip_path(&self) -> Option<PathBuf> {
+        let path = self.fs.resources_dir().parent()?.join("resources.zip");
+        path.is_file().then_some(path)
+    }
 }
 
 fn id_to_path(entry: DirEntry) -> String {
@@ -114,7 +126,286 @@ impl Source for GgezFileSystem {
         let _ = watcher.watch(self.fs.resources_dir().to_owned());
         let _ = watcher.watch(self.fs.user_data_dir().to_owned());
         let _ = watcher.watch(self.fs.user_config_dir().to_owned());
+        watcher.build(events.clone());
+
+        if let Some(zip_path) = self.resources_zip_path() {
+            watch_resources_zip(zip_path, events);
+        }
+
+        Ok(())
+    }
+}
+
+/// The id of every non-directory entry of `resources.zip`, keyed to the CRC
+/// of its (uncompressed) contents so later reads can tell which ones changed.
+fn read_zip_index(path: &Path) -> io::Result<HashMap<String, u32>> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file).map_err(io::Error::other)?;
+
+    let mut index = HashMap::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(io::Error::other)?;
+        if entry.is_dir() {
+            continue;
+        }
+        if let Some((id, _ext)) = tar_path_to_id(Path::new(entry.name())) {
+            index.insert(id, entry.crc32());
+        }
+    }
+
+    Ok(index)
+}
+
+/// Watches `resources.zip` and, on every change, diffs its entries against
+/// the last known state to reload only the ids whose bytes actually changed.
+///
+/// `FsWatcherBuilder` only maps a watched filesystem path to a single id, so
+/// it can't tell us which ids *inside* the archive changed; this needs its
+/// own `notify` watcher instead.
+fn watch_resources_zip(path: PathBuf, events: EventSender) {
+    use notify::Watcher as _;
+
+    let mut previous = read_zip_index(&path).unwrap_or_default();
+
+    let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return };
+        if !event.kind.is_modify() && !event.kind.is_create() {
+            return;
+        }
+
+        let Ok(current) = read_zip_index(&path) else {
+            return;
+        };
+
+        for (id, crc) in &current {
+            if previous.get(id) != Some(crc) {
+                events.send(assets_manager::SharedString::from(id.as_str()));
+            }
+        }
+        for id in previous.keys() {
+            if !current.contains_key(id) {
+                events.send(assets_manager::SharedString::from(id.as_str()));
+            }
+        }
+
+        previous = current;
+    });
+
+    if let Ok(mut watcher) = watcher {
+        if watcher
+            .watch(&path, notify::RecursiveMode::NonRecursive)
+            .is_ok()
+        {
+            // Keep the watcher running for as long as the game does; there's
+            // no natural point at which `GgezFileSystem` is torn down.
+            Box::leak(Box::new(watcher));
+        }
+    }
+}
+
+fn tar_path_to_id(path: &Path) -> Option<(String, String)> {
+    let (name, ext) = split_file_name(path)?;
+
+    let mut id = String::new();
+    if let Some(parent) = path.parent() {
+        for comp in parent.components() {
+            if let std::path::Component::Normal(part) = comp {
+                if !id.is_empty() {
+                    id.push('.');
+                }
+                id.push_str(part.to_str()?);
+            }
+        }
+    }
+    if !id.is_empty() {
+        id.push('.');
+    }
+    id.push_str(name);
+
+    Some((id, ext.to_owned()))
+}
+
+fn register_ancestors(dirs: &mut HashSet<String>, id: &str) {
+    let mut rest = id;
+    while let Some((parent, _)) = rest.rsplit_once('.') {
+        if !dirs.insert(parent.to_owned()) {
+            break;
+        }
+        rest = parent;
+    }
+}
+
+fn is_direct_child(id: &str, parent: &str) -> bool {
+    match id.strip_prefix(parent) {
+        Some(rest) if parent.is_empty() => !rest.is_empty() && !rest.contains('.'),
+        Some(rest) => rest.starts_with('.') && !rest[1..].contains('.'),
+        None => false,
+    }
+}
+
+/// A [`Source`] that reads assets from a single `lz4`-compressed tar
+/// archive, such as one produced by packaging a game's `assets` directory.
+///
+/// This lets a shipped game load its assets from one `assets.pkg` blob
+/// instead of loose files, as an alternative to [`GgezFileSystem`].
+///
+/// The archive is decompressed into memory once, in [`new`](Self::new).
+/// [`configure_hot_reloading`](Source::configure_hot_reloading) only detects
+/// that the underlying file changed on disk; it doesn't re-read it, so a
+/// reload triggered by rebuilding the pack still serves the bytes read at
+/// construction. A game that wants rebuilt packs to actually take effect
+/// needs to create a new `PackedSource` (and `AssetCache`).
+pub struct PackedSource {
+    path: PathBuf,
+    files: HashMap<(String, String), Vec<u8>>,
+    dirs: HashSet<String>,
+}
+
+impl PackedSource {
+    /// Opens an `lz4`-compressed tar archive at `path` and decompresses its
+    /// contents into memory.
+    pub fn new(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let (files, dirs) = Self::read_archive(&path)?;
+        Ok(Self { path, files, dirs })
+    }
+
+    fn read_archive(
+        path: &Path,
+    ) -> io::Result<(HashMap<(String, String), Vec<u8>>, HashSet<String>)> {
+        let file = std::fs::File::open(path)?;
+        let decoder = lz4_flex::frame::FrameDecoder::new(file);
+        let mut archive = tar::Archive::new(decoder);
+
+        let mut files = HashMap::new();
+        let mut dirs = HashSet::new();
+        dirs.insert(String::new());
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.into_owned();
+
+            let Some((id, ext)) = tar_path_to_id(&entry_path) else {
+                continue;
+            };
+
+            if entry.header().entry_type().is_dir() {
+                dirs.insert(id);
+                continue;
+            }
+
+            let mut data = Vec::with_capacity(entry.size() as usize);
+            io::Read::read_to_end(&mut entry, &mut data)?;
+
+            register_ancestors(&mut dirs, &id);
+            files.insert((id, ext), data);
+        }
+
+        Ok((files, dirs))
+    }
+}
+
+impl Source for PackedSource {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        let data = self
+            .files
+            .get(&(id.to_owned(), ext.to_owned()))
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        Ok(FileContent::Slice(data))
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        for (child_id, ext) in self.files.keys() {
+            if is_direct_child(child_id, id) {
+                f(DirEntry::File(child_id, ext));
+            }
+        }
+        for child_id in &self.dirs {
+            if is_direct_child(child_id, id) {
+                f(DirEntry::Directory(child_id));
+            }
+        }
+        Ok(())
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        match entry {
+            DirEntry::File(id, ext) => self.files.contains_key(&(id.to_owned(), ext.to_owned())),
+            DirEntry::Directory(id) => self.dirs.contains(id),
+        }
+    }
+
+    fn configure_hot_reloading(
+        &self,
+        events: EventSender,
+    ) -> Result<(), assets_manager::BoxedError> {
+        // We can't map individual archive members to filesystem paths, so a
+        // change to the packed file is treated as a full reload.
+        let mut watcher = FsWatcherBuilder::new()?;
+        let _ = watcher.watch(self.path.clone());
         watcher.build(events);
         Ok(())
     }
 }
+
+/// A composite [`Source`] that resolves each id by trying its inner sources
+/// in priority order, returning the first hit.
+///
+/// This lets mods or user overrides shadow individual files in a base
+/// [`GgezFileSystem`] or [`PackedSource`] without repacking, and lets a game
+/// search multiple resource roots with deterministic precedence.
+pub struct OverlaySource {
+    layers: Vec<Box<dyn Source>>,
+}
+
+impl OverlaySource {
+    /// Creates a new overlay from a list of sources, in decreasing priority:
+    /// the first layer shadows every one that comes after it.
+    pub fn new(layers: Vec<Box<dyn Source>>) -> Self {
+        Self { layers }
+    }
+}
+
+impl Source for OverlaySource {
+    fn read(&self, id: &str, ext: &str) -> io::Result<FileContent<'_>> {
+        for layer in &self.layers {
+            match layer.read(id, ext) {
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                result => return result,
+            }
+        }
+        Err(io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn read_dir(&self, id: &str, f: &mut dyn FnMut(DirEntry)) -> io::Result<()> {
+        let mut seen = HashSet::new();
+
+        for layer in &self.layers {
+            layer.read_dir(id, &mut |entry| {
+                let key: &str = match &entry {
+                    DirEntry::File(id, _) => *id,
+                    DirEntry::Directory(id) => *id,
+                };
+                if seen.insert(key.to_owned()) {
+                    f(entry);
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn exists(&self, entry: DirEntry) -> bool {
+        self.layers.iter().any(|layer| layer.exists(entry))
+    }
+
+    fn configure_hot_reloading(
+        &self,
+        events: EventSender,
+    ) -> Result<(), assets_manager::BoxedError> {
+        for layer in &self.layers {
+            layer.configure_hot_reloading(events.clone())?;
+        }
+        Ok(())
+    }
+}
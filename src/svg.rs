@@ -0,0 +1,127 @@
+//! An SVG [`GgezAsset`](crate::GgezAsset) that rasterizes to a
+//! [`ggez::graphics::Image`] at load time, gated behind the `svg` feature.
+
+use std::borrow::Cow;
+
+use assets_manager::{asset::FileAsset, Asset, AssetCache, BoxedError, SharedString};
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+/// Selects an SVG source and the raster scale to render it at, so the same
+/// source can be requested at multiple resolutions under distinct ids (e.g.
+/// `"ui.icons.gear"` at `scale: 1.0` and a `"ui.icons.gear_hd"` descriptor
+/// pointing at the same `source` with `scale: 2.0`).
+#[derive(serde::Deserialize, assets_manager::Asset)]
+#[asset_format = "ron"]
+struct SvgImageDescriptor {
+    source: SharedString,
+    #[serde(default = "default_scale")]
+    scale: f32,
+}
+
+/// The raw SVG document, loaded as a plain text file.
+struct SvgSource(String);
+
+impl FileAsset for SvgSource {
+    const EXTENSION: &'static str = "svg";
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Result<Self, BoxedError> {
+        String::from_bytes(bytes).map(SvgSource)
+    }
+}
+
+/// The raw content of an [`SvgImage`]: its source document and the scale it
+/// should be rasterized at.
+#[derive(Clone)]
+pub struct SvgRaw {
+    data: String,
+    scale: f32,
+}
+
+impl Asset for SvgRaw {
+    fn load(cache: &AssetCache, id: &SharedString) -> Result<Self, BoxedError> {
+        let SvgImageDescriptor { source, scale } = SvgImageDescriptor::load(cache, id)?;
+        let SvgSource(data) = SvgSource::load(cache, &source)?;
+        Ok(SvgRaw { data, scale })
+    }
+}
+
+/// An SVG document rasterized to a [`ggez::graphics::Image`] at load time.
+///
+/// The raster resolution is picked by the `scale` field of the RON
+/// descriptor loaded for this id; see [`SvgRaw`].
+pub struct SvgImage(ggez::graphics::Image);
+
+impl SvgImage {
+    /// The rasterized image.
+    #[must_use]
+    pub fn image(&self) -> &ggez::graphics::Image {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for SvgImage {
+    type Target = ggez::graphics::Image;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl crate::GgezAsset for SvgImage {
+    type Raw = SvgRaw;
+
+    fn from_raw(raw: &mut Self::Raw, ctx: &mut ggez::Context) -> ggez::GameResult<Self> {
+        let not_found =
+            |e: usvg::Error| ggez::GameError::ResourceLoadError(format!("invalid SVG: {e}"));
+
+        let tree = usvg::Tree::from_str(&raw.data, &usvg::Options::default())
+            .map_err(not_found)?;
+
+        let size = tree.size();
+        let width = (size.width() * raw.scale).round().max(1.0) as u32;
+        let height = (size.height() * raw.scale).round().max(1.0) as u32;
+
+        let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or_else(|| {
+            ggez::GameError::ResourceLoadError(format!(
+                "invalid raster size {width}x{height} for SVG"
+            ))
+        })?;
+
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(raw.scale, raw.scale),
+            &mut pixmap.as_mut(),
+        );
+
+        // `tiny_skia::Pixmap` stores premultiplied alpha, but `ggez` draws
+        // images with straight alpha; without this, transparent edges come
+        // out with dark fringes.
+        let mut pixels = pixmap.take();
+        for pixel in pixels.chunks_exact_mut(4) {
+            let [r, g, b, a] = [
+                pixel[0] as u32,
+                pixel[1] as u32,
+                pixel[2] as u32,
+                pixel[3] as u32,
+            ];
+            if a > 0 {
+                pixel[0] = (r * 255 / a) as u8;
+                pixel[1] = (g * 255 / a) as u8;
+                pixel[2] = (b * 255 / a) as u8;
+            }
+        }
+
+        let image = ggez::graphics::Image::from_pixels(
+            ctx,
+            &pixels,
+            ggez::graphics::ImageFormat::Rgba8UnormSrgb,
+            width,
+            height,
+        );
+
+        Ok(SvgImage(image))
+    }
+}